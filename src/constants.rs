@@ -3,3 +3,6 @@ pub const EARTH_RADIUS: f64 = 6_378_137.0;
 
 /// Web mercator extent
 pub const WEB_MERCATOR_EXTENT: f64 = std::f64::consts::PI * EARTH_RADIUS;
+
+/// Maximum latitude representable in Web Mercator, beyond which the projection diverges.
+pub const MAX_LATITUDE: f64 = 85.051_128_78;
@@ -5,11 +5,13 @@
 pub mod bbox;
 pub mod constants;
 pub mod tile;
+pub mod tile_bbox_pyramid;
 pub mod tile_iterator;
 pub mod utils;
 
 pub use crate::bbox::*;
 pub use crate::constants::*;
 pub use crate::tile::*;
+pub use crate::tile_bbox_pyramid::*;
 pub use crate::tile_iterator::*;
 pub use crate::utils::*;
@@ -1,6 +1,9 @@
-use crate::{bbox::BBox, constants::WEB_MERCATOR_EXTENT};
+use crate::{
+    bbox::BBox,
+    constants::{EARTH_RADIUS, MAX_LATITUDE, WEB_MERCATOR_EXTENT},
+};
 use itertools::iproduct;
-use std::{error::Error, fmt::Display, str::FromStr};
+use std::{error::Error, f64::consts::PI, fmt::Display, str::FromStr};
 
 /// Map tile
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -10,6 +13,15 @@ pub struct Tile {
     pub y: u32,
 }
 
+/// Tile coordinate scheme, selecting the `y` axis direction used by [`Tile::format_path`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// OSM-style scheme, `y` grows downward from the top.
+    Xyz,
+    /// TMS-style scheme, `y` grows upward from the bottom (see [`Tile::reversed_y`]).
+    Tms,
+}
+
 impl Tile {
     /// Returns reversed y coordinate: 2 ^ zoom - 1 - y
     #[must_use]
@@ -150,9 +162,52 @@ impl Tile {
         })
     }
 
-    /// Sort tiles according to morton code. Currently it does not take the zoom into the account.
+    /// Returns the tile offset by `(dx, dy)` at the same zoom level. Horizontal movement wraps
+    /// around the antimeridian (modulo `2 ^ zoom`), while vertical movement past the poles
+    /// returns `None`.
+    #[must_use]
+    pub fn neighbor(&self, dx: i32, dy: i32) -> Option<Self> {
+        let size = i64::from(1u32 << self.zoom);
+
+        let y = i64::from(self.y) + i64::from(dy);
+
+        if y < 0 || y >= size {
+            return None;
+        }
+
+        let x = (i64::from(self.x) + i64::from(dx)).rem_euclid(size);
+
+        Some(Self {
+            zoom: self.zoom,
+            x: x as u32,
+            y: y as u32,
+        })
+    }
+
+    /// Returns the up-to-8 tiles directly surrounding this tile, omitting neighbors that would
+    /// fall past a pole and, at zoom 0, the wrap-around neighbors that coincide with this tile.
+    #[must_use]
+    pub fn neighbors(&self) -> Vec<Self> {
+        iproduct!(-1..=1, -1..=1)
+            .filter(|&(dx, dy)| (dx, dy) != (0, 0))
+            .filter_map(|(dx, dy)| self.neighbor(dx, dy))
+            .filter(|tile| tile != self)
+            .collect()
+    }
+
+    /// Returns the four children of this tile's parent. For a zoom 0 tile, which has no parent,
+    /// returns the tile itself four times.
+    #[must_use]
+    pub fn siblings(&self) -> [Self; 4] {
+        self.parent().map_or([*self; 4], |parent| parent.children())
+    }
+
+    /// Sort tiles according to morton code, prefixed by zoom so that mixed-zoom slices sort
+    /// deterministically.
     pub fn sort_by_zorder(tiles: &mut [Self]) {
-        tiles.sort_by_cached_key(Self::morton_code);
+        tiles.sort_by_cached_key(|tile| {
+            (u128::from(tile.zoom) << 64) | u128::from(tile.morton_code())
+        });
     }
 
     fn interleave(v: u32) -> u64 {
@@ -165,11 +220,140 @@ impl Tile {
         result
     }
 
-    /// Returns tile's [Morton code](https://en.wikipedia.org/wiki/Z-order_curve).
+    /// Returns tile's [Morton code](https://en.wikipedia.org/wiki/Z-order_curve). Does not take
+    /// zoom into account; see [`Tile::sort_by_zorder`] for a zoom-aware ordering.
     #[must_use]
     pub fn morton_code(&self) -> u64 {
         Self::interleave(self.x) | (Self::interleave(self.y) << 1)
     }
+
+    /// Returns tile's distance along a [Hilbert curve](https://en.wikipedia.org/wiki/Hilbert_curve)
+    /// at its zoom level. Unlike [`Tile::morton_code`], this preserves spatial locality, which
+    /// makes it a better ordering for cache-friendly tile batches.
+    #[must_use]
+    pub fn hilbert_index(&self) -> u64 {
+        let n = 1_u64 << self.zoom;
+
+        let mut x = u64::from(self.x);
+        let mut y = u64::from(self.y);
+        let mut d = 0_u64;
+
+        let mut s = n / 2;
+
+        while s > 0 {
+            let rx = u64::from((x & s) > 0);
+            let ry = u64::from((y & s) > 0);
+
+            d += s * s * ((3 * rx) ^ ry);
+
+            if ry == 0 {
+                if rx == 1 {
+                    x = n - 1 - x;
+                    y = n - 1 - y;
+                }
+
+                std::mem::swap(&mut x, &mut y);
+            }
+
+            s /= 2;
+        }
+
+        d
+    }
+
+    /// Sorts same-zoom tiles by their [`Tile::hilbert_index`].
+    ///
+    /// Mixing tiles of different zoom levels is not an error, but since each tile's index is
+    /// only comparable within its own zoom's grid, the resulting order is not spatially
+    /// meaningful across zooms.
+    pub fn sort_by_hilbert(tiles: &mut [Self]) {
+        tiles.sort_by_cached_key(Self::hilbert_index);
+    }
+
+    /// Returns the tile at `zoom` with the given [`Tile::hilbert_index`].
+    #[must_use]
+    pub fn from_hilbert(zoom: u8, d: u64) -> Self {
+        let n = 1_u64 << zoom;
+
+        let mut t = d;
+        let mut x = 0_u64;
+        let mut y = 0_u64;
+
+        let mut s = 1_u64;
+
+        while s < n {
+            let rx = 1 & (t / 2);
+            let ry = 1 & (t ^ rx);
+
+            if ry == 0 {
+                if rx == 1 {
+                    x = s - 1 - x;
+                    y = s - 1 - y;
+                }
+
+                std::mem::swap(&mut x, &mut y);
+            }
+
+            x += s * rx;
+            y += s * ry;
+
+            t /= 4;
+            s *= 2;
+        }
+
+        Self {
+            zoom,
+            x: x as u32,
+            y: y as u32,
+        }
+    }
+
+    /// Returns tile at given zoom level containing specified WGS84 longitude/latitude in degrees.
+    ///
+    /// Latitude is clamped to `±MAX_LATITUDE` before conversion, and the resulting tile
+    /// coordinates are clamped to the valid `0..2^zoom` range.
+    #[must_use]
+    pub fn from_lon_lat(lon: f64, lat: f64, zoom: u8) -> Self {
+        let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE);
+
+        let (x, y) = lon_lat_to_mercator(lon, lat);
+        let (x, y) = mercator_to_tile_coords(x, y, zoom);
+
+        let max = (1u32 << zoom) - 1;
+
+        Self {
+            zoom,
+            x: x.min(max),
+            y: y.min(max),
+        }
+    }
+
+    /// Returns the tile's center as WGS84 longitude/latitude in degrees.
+    #[must_use]
+    pub fn center_lon_lat(&self) -> (f64, f64) {
+        let bounds = self.bounds(1);
+
+        mercator_to_lon_lat(
+            (bounds.min_x + bounds.max_x) / 2.0,
+            (bounds.min_y + bounds.max_y) / 2.0,
+        )
+    }
+
+    /// Returns the tile's bounds as a `BBox` in WGS84 longitude/latitude degrees.
+    #[must_use]
+    pub fn geo_bounds(&self) -> BBox {
+        let bounds = self.bounds(1);
+
+        let (min_x, max_y) = mercator_to_lon_lat(bounds.min_x, bounds.max_y);
+        let (max_x, min_y) = mercator_to_lon_lat(bounds.max_x, bounds.min_y);
+
+        BBox {
+            min_x,
+            min_y,
+            max_x,
+            max_y,
+        }
+    }
 }
 
 impl Display for Tile {
@@ -189,6 +373,25 @@ pub fn mercator_to_tile_coords(x: f64, y: f64, zoom: u8) -> (u32, u32) {
     )
 }
 
+/// Converts WGS84 longitude/latitude in degrees to web mercator meters.
+#[must_use]
+pub fn lon_lat_to_mercator(lon: f64, lat: f64) -> (f64, f64) {
+    let lat_rad = lat.to_radians();
+
+    (
+        EARTH_RADIUS * lon.to_radians(),
+        EARTH_RADIUS * (PI / 4.0 + lat_rad / 2.0).tan().ln(),
+    )
+}
+
+/// Converts web mercator meters to WGS84 longitude/latitude in degrees.
+#[must_use]
+pub fn mercator_to_lon_lat(x: f64, y: f64) -> (f64, f64) {
+    let lat_rad = 2.0 * (y / EARTH_RADIUS).exp().atan() - PI / 2.0;
+
+    ((x / EARTH_RADIUS).to_degrees(), lat_rad.to_degrees())
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ParseError;
 
@@ -218,6 +421,96 @@ impl FromStr for Tile {
     }
 }
 
+impl Tile {
+    /// Returns the [Bing-style quadkey](https://learn.microsoft.com/en-us/bingmaps/articles/bing-maps-tile-system)
+    /// encoding of the tile. The string length equals the tile's zoom level.
+    #[must_use]
+    pub fn quadkey(&self) -> String {
+        (1..=self.zoom)
+            .rev()
+            .map(|i| {
+                let mask = 1 << (i - 1);
+
+                let mut digit = 0u8;
+
+                if self.x & mask != 0 {
+                    digit += 1;
+                }
+
+                if self.y & mask != 0 {
+                    digit += 2;
+                }
+
+                (b'0' + digit) as char
+            })
+            .collect()
+    }
+
+    /// Parses a tile from its quadkey representation, as produced by [`Tile::quadkey`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] if the string contains characters other than `0`-`3`.
+    pub fn from_quadkey(s: &str) -> Result<Self, ParseError> {
+        let zoom = u8::try_from(s.len()).map_err(|_| ParseError)?;
+
+        let mut x = 0u32;
+        let mut y = 0u32;
+
+        for (index, digit) in s.chars().enumerate() {
+            let mask = 1 << (usize::from(zoom) - 1 - index);
+
+            match digit {
+                '0' => {}
+                '1' => x |= mask,
+                '2' => y |= mask,
+                '3' => {
+                    x |= mask;
+                    y |= mask;
+                }
+                _ => return Err(ParseError),
+            }
+        }
+
+        Ok(Self { zoom, x, y })
+    }
+}
+
+impl Tile {
+    /// Renders `template` for this tile, replacing `{z}`, `{x}` and `{y}` placeholders. Under
+    /// `Scheme::Tms`, `{y}` is substituted with [`Tile::reversed_y`]. Any `{ext}` placeholder is
+    /// left untouched, for callers like [`Tile::fmt_with_ext`] to fill in.
+    ///
+    /// This is the single code path used to build both on-disk cache paths and request URLs.
+    #[must_use]
+    pub fn format_path(&self, template: &str, scheme: Scheme) -> String {
+        let y = match scheme {
+            Scheme::Xyz => self.y,
+            Scheme::Tms => self.reversed_y(),
+        };
+
+        template
+            .replace("{z}", &self.zoom.to_string())
+            .replace("{x}", &self.x.to_string())
+            .replace("{y}", &y.to_string())
+    }
+
+    /// Formats the tile as `z<sep>x<sep>y` (XYZ scheme), using `sep` or `/` when `None`.
+    #[must_use]
+    pub fn fmt_zxy(&self, sep: Option<&str>) -> String {
+        let sep = sep.unwrap_or("/");
+
+        self.format_path(&format!("{{z}}{sep}{{x}}{sep}{{y}}"), Scheme::Xyz)
+    }
+
+    /// Formats the tile as `z/x/y.ext` (XYZ scheme).
+    #[must_use]
+    pub fn fmt_with_ext(&self, ext: &str) -> String {
+        self.format_path("{z}/{x}/{y}.{ext}", Scheme::Xyz)
+            .replace("{ext}", ext)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -255,6 +548,110 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_quadkey() {
+        assert_eq!(TILE.quadkey(), "021");
+        assert_eq!(Tile::from_quadkey("021"), Ok(TILE));
+    }
+
+    #[test]
+    fn test_from_quadkey_invalid() {
+        assert_eq!(Tile::from_quadkey("049"), Err(ParseError));
+    }
+
+    #[test]
+    fn test_format_path() {
+        assert_eq!(TILE.fmt_zxy(None), "3/1/2");
+        assert_eq!(TILE.fmt_zxy(Some("-")), "3-1-2");
+        assert_eq!(TILE.fmt_with_ext("png"), "3/1/2.png");
+
+        assert_eq!(
+            TILE.format_path("{z}/{x}/{y}", Scheme::Tms),
+            format!("3/1/{}", TILE.reversed_y())
+        );
+    }
+
+    #[test]
+    fn test_from_lon_lat() {
+        assert_eq!(Tile::from_lon_lat(0.0, 0.0, 1), Tile { zoom: 1, x: 1, y: 1 });
+    }
+
+    #[test]
+    fn test_geo_bounds_roundtrip() {
+        let bounds = TILE.geo_bounds();
+
+        let (lon, lat) = TILE.center_lon_lat();
+
+        assert!(lon > bounds.min_x && lon < bounds.max_x);
+        assert!(lat > bounds.min_y && lat < bounds.max_y);
+    }
+
+    #[test]
+    fn test_neighbor_wraps_horizontally() {
+        let tile = Tile { zoom: 2, x: 0, y: 1 };
+
+        assert_eq!(tile.neighbor(-1, 0), Some(Tile { zoom: 2, x: 3, y: 1 }));
+        assert_eq!(tile.neighbor(1, 0), Some(Tile { zoom: 2, x: 1, y: 1 }));
+    }
+
+    #[test]
+    fn test_neighbor_stops_at_poles() {
+        let tile = Tile { zoom: 2, x: 0, y: 0 };
+
+        assert_eq!(tile.neighbor(0, -1), None);
+    }
+
+    #[test]
+    fn test_neighbors_count() {
+        assert_eq!(TILE.neighbors().len(), 8);
+        assert_eq!(Tile { zoom: 2, x: 0, y: 0 }.neighbors().len(), 5);
+    }
+
+    #[test]
+    fn test_neighbors_zoom_0_is_empty() {
+        assert_eq!(Tile { zoom: 0, x: 0, y: 0 }.neighbors(), Vec::new());
+    }
+
+    #[test]
+    fn test_siblings() {
+        assert_eq!(TILE.siblings(), TILE.parent().unwrap().children());
+    }
+
+    #[test]
+    fn test_hilbert_roundtrip() {
+        for zoom in 1..=4 {
+            let n = 1_u32 << zoom;
+
+            for x in 0..n {
+                for y in 0..n {
+                    let tile = Tile { zoom, x, y };
+
+                    assert_eq!(Tile::from_hilbert(zoom, tile.hilbert_index()), tile);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_by_hilbert_is_locality_preserving() {
+        let mut tiles = [
+            Tile { zoom: 2, x: 0, y: 0 },
+            Tile { zoom: 2, x: 3, y: 3 },
+            Tile { zoom: 2, x: 1, y: 0 },
+        ];
+
+        Tile::sort_by_hilbert(&mut tiles);
+
+        assert_eq!(
+            tiles,
+            [
+                Tile { zoom: 2, x: 0, y: 0 },
+                Tile { zoom: 2, x: 1, y: 0 },
+                Tile { zoom: 2, x: 3, y: 3 },
+            ]
+        );
+    }
+
     #[test]
     fn test_children() {
         let expect: [Tile; 4] = [
@@ -0,0 +1,169 @@
+use crate::{bbox::BBox, tile::Tile, tile_iterator::TileIterator, utils::tile_range_for_bbox};
+
+/// Multi-zoom cut of tile space: for each zoom level it stores the inclusive tile x/y range
+/// covered, if any.
+pub struct TileBBoxPyramid {
+    levels: Vec<Option<(u32, u32, u32, u32)>>,
+}
+
+impl TileBBoxPyramid {
+    /// Creates a pyramid with no tiles covered at any level up to `max_zoom`.
+    #[must_use]
+    pub fn new_empty(max_zoom: u8) -> Self {
+        Self {
+            levels: vec![None; usize::from(max_zoom) + 1],
+        }
+    }
+
+    /// Creates a pyramid covering the whole world at every level up to `max_zoom`.
+    #[must_use]
+    pub fn new_full(max_zoom: u8) -> Self {
+        let levels = (0..=max_zoom)
+            .map(|zoom| {
+                let max = (1_u32 << zoom) - 1;
+
+                Some((0, 0, max, max))
+            })
+            .collect();
+
+        Self { levels }
+    }
+
+    /// Builds a pyramid covering the specified web mercator bounding box (see
+    /// [`crate::utils::bbox_covered_tiles`]) across `min_zoom..=max_zoom`.
+    #[must_use]
+    pub fn from_mercator_bbox(bbox: &BBox, min_zoom: u8, max_zoom: u8) -> Self {
+        let mut pyramid = Self::new_empty(max_zoom);
+
+        for zoom in min_zoom..=max_zoom {
+            let (min_x, min_y, max_x, max_y) = tile_range_for_bbox(bbox, zoom);
+
+            pyramid.levels[usize::from(zoom)] = Some((min_x, min_y, max_x, max_y));
+        }
+
+        pyramid
+    }
+
+    /// Clips this pyramid to the per-level intersection with `other`. Levels present in only
+    /// one of the two pyramids become uncovered.
+    pub fn intersect(&mut self, other: &Self) {
+        for level in self.levels.iter_mut().skip(other.levels.len()) {
+            *level = None;
+        }
+
+        for (level, other_level) in self.levels.iter_mut().zip(&other.levels) {
+            *level = match (*level, other_level) {
+                (Some((min_x, min_y, max_x, max_y)), Some((o_min_x, o_min_y, o_max_x, o_max_y))) => {
+                    let min_x = min_x.max(*o_min_x);
+                    let min_y = min_y.max(*o_min_y);
+                    let max_x = max_x.min(*o_max_x);
+                    let max_y = max_y.min(*o_max_y);
+
+                    (min_x <= max_x && min_y <= max_y).then_some((min_x, min_y, max_x, max_y))
+                }
+                _ => None,
+            };
+        }
+    }
+
+    /// Expands the tile window at `tile.zoom` to include `tile`, growing the pyramid if needed.
+    pub fn include_tile(&mut self, tile: Tile) {
+        let zoom = usize::from(tile.zoom);
+
+        if self.levels.len() <= zoom {
+            self.levels.resize(zoom + 1, None);
+        }
+
+        self.levels[zoom] = Some(match self.levels[zoom] {
+            Some((min_x, min_y, max_x, max_y)) => (
+                min_x.min(tile.x),
+                min_y.min(tile.y),
+                max_x.max(tile.x),
+                max_y.max(tile.y),
+            ),
+            None => (tile.x, tile.y, tile.x, tile.y),
+        });
+    }
+
+    /// Returns the inclusive tile x/y range covered at `zoom`, if any.
+    #[must_use]
+    pub fn level_bbox(&self, zoom: u8) -> Option<(u32, u32, u32, u32)> {
+        self.levels.get(usize::from(zoom)).copied().flatten()
+    }
+
+    /// Returns the total number of tiles covered across all levels.
+    #[must_use]
+    pub fn tile_count(&self) -> u64 {
+        self.levels
+            .iter()
+            .filter_map(|level| *level)
+            .map(|(min_x, min_y, max_x, max_y)| {
+                u64::from(max_x - min_x + 1) * u64::from(max_y - min_y + 1)
+            })
+            .sum()
+    }
+}
+
+impl IntoIterator for TileBBoxPyramid {
+    type Item = Tile;
+    type IntoIter = Box<dyn Iterator<Item = Tile>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Box::new(
+            self.levels
+                .into_iter()
+                .enumerate()
+                .filter_map(|(zoom, level)| {
+                    level.map(|(min_x, min_y, max_x, max_y)| {
+                        TileIterator::new(zoom as u8, min_x..=max_x, min_y..=max_y)
+                    })
+                })
+                .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_mercator_bbox_and_iter() {
+        let pyramid = TileBBoxPyramid::from_mercator_bbox(
+            &BBox::new(1_137_489.0, 5_980_732.0, 1_711_100.0, 6_428_543.0),
+            6,
+            7,
+        );
+
+        assert_eq!(pyramid.level_bbox(6), Some((33, 21, 34, 22)));
+        assert_eq!(pyramid.level_bbox(7), Some((67, 43, 69, 44)));
+        assert_eq!(pyramid.level_bbox(5), None);
+
+        assert_eq!(pyramid.tile_count(), 2 * 2 + 3 * 2);
+        assert_eq!(pyramid.into_iter().count(), 4 + 6);
+    }
+
+    #[test]
+    fn test_intersect() {
+        let mut a = TileBBoxPyramid::new_empty(2);
+        a.include_tile(Tile { zoom: 2, x: 1, y: 1 });
+        a.include_tile(Tile { zoom: 2, x: 3, y: 3 });
+
+        let mut b = TileBBoxPyramid::new_empty(2);
+        b.include_tile(Tile { zoom: 2, x: 2, y: 2 });
+        b.include_tile(Tile { zoom: 2, x: 3, y: 3 });
+
+        a.intersect(&b);
+
+        assert_eq!(a.level_bbox(2), Some((2, 2, 3, 3)));
+    }
+
+    #[test]
+    fn test_include_tile_grows_pyramid() {
+        let mut pyramid = TileBBoxPyramid::new_empty(0);
+
+        pyramid.include_tile(Tile { zoom: 3, x: 2, y: 5 });
+
+        assert_eq!(pyramid.level_bbox(3), Some((2, 5, 2, 5)));
+    }
+}
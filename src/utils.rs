@@ -2,9 +2,8 @@ use std::ops::RangeInclusive;
 
 use crate::{bbox::BBox, constants::WEB_MERCATOR_EXTENT, tile_iterator::TileIterator};
 
-/// Returns all tiles covering specified web mercator bounding box at specified zoom.
-#[must_use]
-pub fn bbox_covered_tiles(bbox: &BBox, zoom: u8) -> TileIterator<RangeInclusive<u32>> {
+/// Computes the inclusive tile x/y range covering a web mercator bounding box at a given zoom.
+pub(crate) fn tile_range_for_bbox(bbox: &BBox, zoom: u8) -> (u32, u32, u32, u32) {
     let tile_size_meters = (WEB_MERCATOR_EXTENT * 2.0) / f64::from(1 << zoom);
 
     // Compute the tile range for the given bounding box
@@ -13,6 +12,14 @@ pub fn bbox_covered_tiles(bbox: &BBox, zoom: u8) -> TileIterator<RangeInclusive<
     let min_tile_y = ((WEB_MERCATOR_EXTENT - bbox.max_y) / tile_size_meters).floor() as u32;
     let max_tile_y = ((WEB_MERCATOR_EXTENT - bbox.min_y) / tile_size_meters).ceil() as u32 - 1;
 
+    (min_tile_x, min_tile_y, max_tile_x, max_tile_y)
+}
+
+/// Returns all tiles covering specified web mercator bounding box at specified zoom.
+#[must_use]
+pub fn bbox_covered_tiles(bbox: &BBox, zoom: u8) -> TileIterator<RangeInclusive<u32>> {
+    let (min_tile_x, min_tile_y, max_tile_x, max_tile_y) = tile_range_for_bbox(bbox, zoom);
+
     TileIterator::new(zoom, min_tile_x..=max_tile_x, min_tile_y..=max_tile_y)
 }
 